@@ -0,0 +1,440 @@
+use crate::SolverSettings;
+use clarabel::algebra::*;
+use clarabel::solver::*;
+use serde::Serialize;
+use std::panic;
+use wasm_bindgen::prelude::*;
+
+/// A linear constraint row, stored as sparse `(col, value)` coefficients
+/// until `RowProblem::solve` assembles the CSC matrices.
+struct RowSpec {
+    coeffs: Vec<(usize, f64)>,
+    lower: f64,
+    upper: f64,
+}
+
+/// Solution of a `RowProblem`, with values keyed by the column/row indices
+/// handed back from `add_variable`/`add_row`.
+#[derive(Serialize)]
+pub struct RowProblemSolution {
+    /// Same status strings as `SolveResult`.
+    pub status: String,
+    /// Optimal objective value (if solved)
+    pub obj_val: Option<f64>,
+    /// Primal variable values, in column order (index = `add_variable` return value)
+    pub x: Option<Vec<f64>>,
+    /// Dual values for each explicit row, in row order (index = `add_row` return value).
+    /// For a two-sided row (both `lower` and `upper` finite), this is the
+    /// combined ranged-constraint dual `z_upper - z_lower`.
+    pub row_duals: Option<Vec<f64>>,
+    /// Infeasibility certificate, same semantics as `SolveResult::certificate`
+    pub certificate: Option<Vec<f64>>,
+    /// Solve time in seconds
+    pub solve_time: f64,
+    /// Number of iterations
+    pub iterations: u32,
+}
+
+fn error_solution(msg: String) -> RowProblemSolution {
+    RowProblemSolution {
+        status: format!("error: {}", msg),
+        obj_val: None,
+        x: None,
+        row_duals: None,
+        certificate: None,
+        solve_time: 0.0,
+        iterations: 0,
+    }
+}
+
+/// Assemble column-major CSC data from `(row, col, value)` triplets.
+/// Entries need not be pre-sorted.
+fn triplets_to_csc(nrows: usize, ncols: usize, mut triplets: Vec<(usize, usize, f64)>) -> CscMatrix<f64> {
+    triplets.sort_by_key(|&(row, col, _)| (col, row));
+
+    let mut col_ptr = vec![0usize; ncols + 1];
+    for &(_, col, _) in &triplets {
+        col_ptr[col + 1] += 1;
+    }
+    for i in 0..ncols {
+        col_ptr[i + 1] += col_ptr[i];
+    }
+
+    let row_idx: Vec<usize> = triplets.iter().map(|&(row, _, _)| row).collect();
+    let values: Vec<f64> = triplets.iter().map(|&(_, _, v)| v).collect();
+
+    CscMatrix::new(nrows, ncols, col_ptr, row_idx, values)
+}
+
+/// High-level, row-oriented modeling layer over the raw CSC `solve()` interface.
+///
+/// Mirrors HiGHS's `RowProblem`: callers add variables and linear rows by
+/// index instead of hand-assembling CSC matrices and a cone partition.
+/// Variable bounds and row bounds are translated into `ZeroConeT`/
+/// `NonnegativeConeT` blocks internally.
+#[wasm_bindgen]
+pub struct RowProblem {
+    obj_coeffs: Vec<f64>,
+    var_bounds: Vec<(f64, f64)>,
+    quad_terms: Vec<(usize, usize, f64)>,
+    rows: Vec<RowSpec>,
+    /// First validation error encountered by `add_variable`/`add_row`, if
+    /// any. Checked at the start of `solve()` so a bad call site gets a
+    /// clean error `RowProblemSolution` instead of a panic deep inside
+    /// CSC assembly.
+    error: Option<String>,
+}
+
+impl Default for RowProblem {
+    fn default() -> Self {
+        RowProblem {
+            obj_coeffs: Vec::new(),
+            var_bounds: Vec::new(),
+            quad_terms: Vec::new(),
+            rows: Vec::new(),
+            error: None,
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl RowProblem {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> RowProblem {
+        RowProblem::default()
+    }
+
+    /// Add a variable with linear objective coefficient `obj_coeff` and bounds
+    /// `[lower, upper]` (use `-Infinity`/`Infinity` for one-sided or free
+    /// variables). Returns the variable's column index.
+    ///
+    /// An invalid bound (`lower > upper`) is recorded and reported as an
+    /// error `RowProblemSolution` from the next `solve()` call, rather than
+    /// failing here.
+    pub fn add_variable(&mut self, obj_coeff: f64, lower: f64, upper: f64) -> usize {
+        let col = self.obj_coeffs.len();
+        if lower > upper {
+            self.error.get_or_insert_with(|| {
+                format!(
+                    "add_variable: lower ({}) > upper ({}) for variable {}",
+                    lower, upper, col
+                )
+            });
+        }
+        self.obj_coeffs.push(obj_coeff);
+        self.var_bounds.push((lower, upper));
+        col
+    }
+
+    /// Add a linear constraint row `sum(cols[i] * values[i]) in [lower, upper]`
+    /// (use `-Infinity`/`Infinity` for one-sided rows, or `lower == upper` for
+    /// an equality). Returns the row's index.
+    ///
+    /// `cols`/`values` must have equal length and every column must refer to
+    /// an already-added variable; an invalid bound (`lower > upper`) is also
+    /// rejected. Any violation is recorded and reported as an error
+    /// `RowProblemSolution` from the next `solve()` call, rather than
+    /// panicking here or during CSC assembly.
+    pub fn add_row(&mut self, cols: &[u32], values: &[f64], lower: f64, upper: f64) -> usize {
+        let row_idx = self.rows.len();
+        let n = self.obj_coeffs.len();
+
+        if cols.len() != values.len() {
+            self.error.get_or_insert_with(|| {
+                format!(
+                    "add_row: cols.len() ({}) != values.len() ({}) for row {}",
+                    cols.len(),
+                    values.len(),
+                    row_idx
+                )
+            });
+        } else if let Some(&bad) = cols.iter().find(|&&c| c as usize >= n) {
+            self.error.get_or_insert_with(|| {
+                format!(
+                    "add_row: column index {} out of range (0..{}) for row {}",
+                    bad, n, row_idx
+                )
+            });
+        } else if lower > upper {
+            self.error.get_or_insert_with(|| {
+                format!(
+                    "add_row: lower ({}) > upper ({}) for row {}",
+                    lower, upper, row_idx
+                )
+            });
+        }
+
+        let coeffs = cols
+            .iter()
+            .zip(values.iter())
+            .map(|(&c, &v)| (c as usize, v))
+            .collect();
+        self.rows.push(RowSpec { coeffs, lower, upper });
+        row_idx
+    }
+
+    /// Register a quadratic objective term by storing `value` as the
+    /// upper-triangle entry `P[i][j]` of the `(1/2) x' P x` term: for `i != j`
+    /// this adds `value * x_i * x_j` to the objective, for `i == j` it adds
+    /// `(value / 2) * x_i^2`.
+    ///
+    /// `i`/`j` must refer to already-added variables; an out-of-range index
+    /// is recorded and reported as an error `RowProblemSolution` from the
+    /// next `solve()` call, rather than panicking during CSC assembly.
+    pub fn add_quad_term(&mut self, i: usize, j: usize, value: f64) {
+        let n = self.obj_coeffs.len();
+        if i >= n || j >= n {
+            self.error.get_or_insert_with(|| {
+                format!("add_quad_term: index ({}, {}) out of range (0..{})", i, j, n)
+            });
+        }
+        let (i, j) = if i <= j { (i, j) } else { (j, i) };
+        self.quad_terms.push((i, j, value));
+    }
+
+    /// Assemble the CSC matrices and cone partition, solve, and map the
+    /// result back onto the original variable/row indices.
+    pub fn solve(&self, settings_json: &str) -> JsValue {
+        let settings: SolverSettings = serde_json::from_str(settings_json).unwrap_or_default();
+        serde_wasm_bindgen::to_value(&self.solve_with(&settings)).unwrap()
+    }
+
+    /// Core of `solve()`, split out so it can be exercised in tests without
+    /// going through the `JsValue` boundary.
+    fn solve_with(&self, settings: &SolverSettings) -> RowProblemSolution {
+        if let Some(msg) = &self.error {
+            return error_solution(msg.clone());
+        }
+
+        let n = self.obj_coeffs.len();
+
+        let p = triplets_to_csc(n, n, self.quad_terms.clone());
+        let q = self.obj_coeffs.clone();
+
+        // Equality rows (explicit + variable bounds) go into a ZeroConeT
+        // block first; inequality rows go into a single NonnegativeConeT
+        // block after, matching the contiguous cone-block ordering Clarabel
+        // expects.
+        let mut eq_triplets = Vec::new();
+        let mut eq_b = Vec::new();
+        let mut ineq_triplets = Vec::new();
+        let mut ineq_b = Vec::new();
+
+        // Row index (into `row_duals`) for each equality entry we emit, so we
+        // can scatter the returned duals back to the caller's row order.
+        let mut eq_row_owner = Vec::new();
+        // Same, but paired with a sign: a two-sided row emits both a
+        // lower-bound entry (sign -1, since its coefficients were negated)
+        // and an upper-bound entry (sign +1); their duals are summed so the
+        // combined dual is `z_upper - z_lower`.
+        let mut ineq_row_owner: Vec<(usize, f64)> = Vec::new();
+
+        for (row_idx, row) in self.rows.iter().enumerate() {
+            if row.lower == row.upper {
+                let r = eq_b.len();
+                for &(col, val) in &row.coeffs {
+                    eq_triplets.push((r, col, val));
+                }
+                eq_b.push(row.lower);
+                eq_row_owner.push(row_idx);
+            } else {
+                if row.lower.is_finite() {
+                    let r = ineq_b.len();
+                    for &(col, val) in &row.coeffs {
+                        ineq_triplets.push((r, col, -val));
+                    }
+                    ineq_b.push(-row.lower);
+                    ineq_row_owner.push((row_idx, -1.0));
+                }
+                if row.upper.is_finite() {
+                    let r = ineq_b.len();
+                    for &(col, val) in &row.coeffs {
+                        ineq_triplets.push((r, col, val));
+                    }
+                    ineq_b.push(row.upper);
+                    ineq_row_owner.push((row_idx, 1.0));
+                }
+            }
+        }
+
+        for (col, &(lower, upper)) in self.var_bounds.iter().enumerate() {
+            if lower == upper {
+                let r = eq_b.len();
+                eq_triplets.push((r, col, 1.0));
+                eq_b.push(lower);
+            } else {
+                if lower.is_finite() {
+                    let r = ineq_b.len();
+                    ineq_triplets.push((r, col, -1.0));
+                    ineq_b.push(-lower);
+                }
+                if upper.is_finite() {
+                    let r = ineq_b.len();
+                    ineq_triplets.push((r, col, 1.0));
+                    ineq_b.push(upper);
+                }
+            }
+        }
+
+        let m = eq_b.len() + ineq_b.len();
+        let mut a_triplets = eq_triplets;
+        for (r, col, val) in ineq_triplets {
+            a_triplets.push((r + eq_b.len(), col, val));
+        }
+        let a = triplets_to_csc(m, n, a_triplets);
+
+        let mut b = eq_b;
+        b.extend(ineq_b);
+
+        let eq_count = eq_row_owner.len() + self.var_bounds.iter().filter(|&&(l, u)| l == u).count();
+        let ineq_count = m - eq_count;
+
+        let mut cones: Vec<SupportedConeT<f64>> = Vec::new();
+        if eq_count > 0 {
+            cones.push(ZeroConeT(eq_count));
+        }
+        if ineq_count > 0 {
+            cones.push(NonnegativeConeT(ineq_count));
+        }
+
+        let time_limit = if settings.time_limit.is_infinite() {
+            1e10
+        } else {
+            settings.time_limit
+        };
+
+        let solver_settings = match DefaultSettingsBuilder::default()
+            .verbose(settings.verbose)
+            .max_iter(settings.max_iter)
+            .time_limit(time_limit)
+            .tol_gap_abs(settings.tol_gap_abs)
+            .tol_gap_rel(settings.tol_gap_rel)
+            .build()
+        {
+            Ok(s) => s,
+            Err(e) => {
+                return error_solution(format!("invalid solver settings: {}", e));
+            }
+        };
+
+        let solve_result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+            let mut solver = DefaultSolver::new(&p, &q, &a, &b, &cones, solver_settings);
+            solver.solve();
+
+            let solution = &solver.solution;
+            let info = &solver.info;
+
+            let is_primal_infeasible = matches!(
+                solution.status,
+                SolverStatus::PrimalInfeasible | SolverStatus::AlmostPrimalInfeasible
+            );
+            let is_dual_infeasible = matches!(
+                solution.status,
+                SolverStatus::DualInfeasible | SolverStatus::AlmostDualInfeasible
+            );
+            // AlmostSolved still carries a usable (if less accurate) point,
+            // so surface it alongside Solved instead of dropping it to None
+            // (mirrors `extract_solve_result` in lib.rs).
+            let has_point = matches!(
+                solution.status,
+                SolverStatus::Solved | SolverStatus::AlmostSolved
+            );
+
+            // Scatter the equality/inequality duals back onto the caller's
+            // original row order; a two-sided row's lower- and upper-bound
+            // entries are summed with sign into that row's combined dual.
+            let mut row_duals = vec![0.0; self.rows.len()];
+            if has_point {
+                for (i, &row_idx) in eq_row_owner.iter().enumerate() {
+                    row_duals[row_idx] = solution.z[i];
+                }
+                for (i, &(row_idx, sign)) in ineq_row_owner.iter().enumerate() {
+                    row_duals[row_idx] += sign * solution.z[eq_count + i];
+                }
+            }
+
+            RowProblemSolution {
+                status: crate::status_to_string(solution.status),
+                obj_val: if has_point { Some(solution.obj_val) } else { None },
+                x: if has_point { Some(solution.x.clone()) } else { None },
+                row_duals: if has_point { Some(row_duals) } else { None },
+                certificate: if is_primal_infeasible {
+                    Some(solution.z.clone())
+                } else if is_dual_infeasible {
+                    Some(solution.x.clone())
+                } else {
+                    None
+                },
+                solve_time: info.solve_time,
+                iterations: info.iterations,
+            }
+        }));
+
+        solve_result.unwrap_or_else(|e| {
+            let msg = if let Some(s) = e.downcast_ref::<&str>() {
+                s.to_string()
+            } else if let Some(s) = e.downcast_ref::<String>() {
+                s.clone()
+            } else {
+                "Unknown panic".to_string()
+            };
+            error_solution(format!("solver panic: {}", msg))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // `SolverSettings`'s `#[derive(Default)]` gives zeroed numeric fields
+    // (no iterations, no time budget); go through the same JSON-default
+    // path `solve()` uses so these tests exercise a real solver config.
+    fn default_settings() -> SolverSettings {
+        serde_json::from_str("{}").unwrap()
+    }
+
+    #[test]
+    fn add_row_rejects_out_of_range_column() {
+        let mut problem = RowProblem::new();
+        problem.add_variable(1.0, 0.0, 1.0);
+        problem.add_row(&[1], &[1.0], 0.0, 1.0); // column 1 doesn't exist
+
+        let result = problem.solve_with(&default_settings());
+        assert!(result.status.starts_with("error:"));
+    }
+
+    #[test]
+    fn add_row_rejects_mismatched_lengths() {
+        let mut problem = RowProblem::new();
+        problem.add_variable(1.0, 0.0, 1.0);
+        problem.add_row(&[0], &[1.0, 2.0], 0.0, 1.0);
+
+        let result = problem.solve_with(&default_settings());
+        assert!(result.status.starts_with("error:"));
+    }
+
+    #[test]
+    fn add_variable_rejects_inverted_bounds() {
+        let mut problem = RowProblem::new();
+        problem.add_variable(1.0, 5.0, 1.0);
+
+        let result = problem.solve_with(&default_settings());
+        assert!(result.status.starts_with("error:"));
+    }
+
+    #[test]
+    fn ranged_row_dual_is_not_silently_zeroed() {
+        // minimize x s.t. 1 <= x <= 5; the lower bound is active at x = 1,
+        // so the combined ranged dual must be nonzero (previously the
+        // upper-bound row's inactive dual of 0.0 overwrote it).
+        let mut problem = RowProblem::new();
+        problem.add_variable(1.0, f64::NEG_INFINITY, f64::INFINITY);
+        problem.add_row(&[0], &[1.0], 1.0, 5.0);
+
+        let result = problem.solve_with(&default_settings());
+        assert_eq!(result.status, "optimal");
+        let row_duals = result.row_duals.expect("solved problem has row duals");
+        assert_ne!(row_duals[0], 0.0);
+    }
+}
@@ -4,10 +4,15 @@ use clarabel::solver::*;
 use serde::{Deserialize, Serialize};
 use std::panic;
 
+mod builder;
+pub use builder::RowProblem;
+
 /// Result of solving an optimization problem
 #[derive(Serialize, Deserialize)]
 pub struct SolveResult {
-    /// Status: "optimal", "infeasible", "unbounded", "max_iterations", "unknown"
+    /// Status: "optimal", "infeasible", "unbounded", "solved_inaccurate",
+    /// "primal_infeasible_inaccurate", "dual_infeasible_inaccurate",
+    /// "max_iterations", "max_time", "unknown"
     pub status: String,
     /// Optimal objective value (if solved)
     pub obj_val: Option<f64>,
@@ -15,6 +20,12 @@ pub struct SolveResult {
     pub x: Option<Vec<f64>>,
     /// Dual solution vector (shadow prices for constraints)
     pub z: Option<Vec<f64>>,
+    /// Constraint slacks `s` such that `A x + s = b`
+    pub s: Option<Vec<f64>>,
+    /// Infeasibility certificate: the primal-infeasibility direction `z` when
+    /// the problem is primal-infeasible, or the unbounded ray `x` when
+    /// dual-infeasible. `None` otherwise.
+    pub certificate: Option<Vec<f64>>,
     /// Solve time in seconds
     pub solve_time: f64,
     /// Number of iterations
@@ -34,6 +45,10 @@ pub struct ConeSpec {
     pub exp: usize,
     /// Power cone alphas (each cone is 3D)
     pub power: Vec<f64>,
+    /// Generalized power cone (alpha vector, w-block dimension) pairs.
+    /// Each cone has total dimension `alpha.len() + dim`.
+    #[serde(default)]
+    pub genpower: Vec<(Vec<f64>, usize)>,
 }
 
 /// Solver settings from JavaScript
@@ -55,8 +70,11 @@ fn default_max_iter() -> u32 { 100 }
 fn default_time_limit() -> f64 { f64::INFINITY }
 fn default_tol() -> f64 { 1e-8 }
 
-/// Build Clarabel cone specification from our ConeSpec
-fn build_cones(spec: &ConeSpec) -> Vec<SupportedConeT<f64>> {
+/// Build Clarabel cone specification from our ConeSpec.
+///
+/// Returns an error string (suitable for the invalid-cone-spec JSON path)
+/// if a generalized power cone's alpha vector is malformed.
+fn build_cones(spec: &ConeSpec) -> Result<Vec<SupportedConeT<f64>>, String> {
     let mut cones = Vec::new();
 
     if spec.zero > 0 {
@@ -79,17 +97,34 @@ fn build_cones(spec: &ConeSpec) -> Vec<SupportedConeT<f64>> {
         cones.push(PowerConeT(alpha));
     }
 
-    cones
+    for (alphas, dim) in &spec.genpower {
+        if alphas.iter().any(|&a| a <= 0.0) {
+            return Err("genpower cone alpha entries must all be positive".to_string());
+        }
+        let sum: f64 = alphas.iter().sum();
+        if (sum - 1.0).abs() > 1e-8 {
+            return Err(format!(
+                "genpower cone alpha vector must sum to 1.0, got {}",
+                sum
+            ));
+        }
+        cones.push(GenPowerConeT(alphas.clone(), *dim));
+    }
+
+    Ok(cones)
 }
 
 /// Convert solver status to string
-fn status_to_string(status: SolverStatus) -> String {
+pub(crate) fn status_to_string(status: SolverStatus) -> String {
     match status {
         SolverStatus::Solved => "optimal".to_string(),
         SolverStatus::PrimalInfeasible => "infeasible".to_string(),
         SolverStatus::DualInfeasible => "unbounded".to_string(),
+        SolverStatus::AlmostSolved => "solved_inaccurate".to_string(),
+        SolverStatus::AlmostPrimalInfeasible => "primal_infeasible_inaccurate".to_string(),
+        SolverStatus::AlmostDualInfeasible => "dual_infeasible_inaccurate".to_string(),
         SolverStatus::MaxIterations => "max_iterations".to_string(),
-        SolverStatus::MaxTime => "max_iterations".to_string(),
+        SolverStatus::MaxTime => "max_time".to_string(),
         _ => "unknown".to_string(),
     }
 }
@@ -143,6 +178,8 @@ pub fn solve(
                 obj_val: None,
                 x: None,
                 z: None,
+                s: None,
+                certificate: None,
                 solve_time: 0.0,
                 iterations: 0,
             };
@@ -172,7 +209,22 @@ pub fn solve(
     );
 
     // Build cones
-    let cones = build_cones(&cone_spec);
+    let cones = match build_cones(&cone_spec) {
+        Ok(cones) => cones,
+        Err(e) => {
+            let result = SolveResult {
+                status: format!("error: invalid cone spec: {}", e),
+                obj_val: None,
+                x: None,
+                z: None,
+                s: None,
+                certificate: None,
+                solve_time: 0.0,
+                iterations: 0,
+            };
+            return serde_wasm_bindgen::to_value(&result).unwrap();
+        }
+    };
 
     // Use a large but finite time_limit instead of infinity (WASM compatibility)
     let time_limit = if settings.time_limit.is_infinite() {
@@ -195,57 +247,238 @@ pub fn solve(
         // Create and solve
         let mut solver = DefaultSolver::new(&p, q, &a, b, &cones, solver_settings);
         solver.solve();
-
-        // Extract solution
-        let solution = &solver.solution;
-        let info = &solver.info;
-
-        SolveResult {
-            status: status_to_string(solution.status),
-            obj_val: if solution.status == SolverStatus::Solved {
-                Some(solution.obj_val)
-            } else {
-                None
-            },
-            x: if solution.status == SolverStatus::Solved {
-                Some(solution.x.clone())
-            } else {
-                None
-            },
-            z: if solution.status == SolverStatus::Solved {
-                Some(solution.z.clone())
-            } else {
-                None
-            },
-            solve_time: info.solve_time,
-            iterations: info.iterations,
-        }
+        extract_solve_result(&solver)
     }));
 
-    let result = match solve_result {
-        Ok(result) => result,
-        Err(e) => {
-            let msg = if let Some(s) = e.downcast_ref::<&str>() {
-                s.to_string()
-            } else if let Some(s) = e.downcast_ref::<String>() {
-                s.clone()
-            } else {
-                "Unknown panic".to_string()
-            };
-            SolveResult {
-                status: format!("error: solver panic: {}", msg),
-                obj_val: None,
-                x: None,
-                z: None,
-                solve_time: 0.0,
-                iterations: 0,
-            }
-        }
-    };
+    let result = solve_result.unwrap_or_else(panic_to_solve_result);
 
     serde_wasm_bindgen::to_value(&result).unwrap()
 }
 
+/// Extract a `SolveResult` from a solved `DefaultSolver`.
+fn extract_solve_result(solver: &DefaultSolver) -> SolveResult {
+    let solution = &solver.solution;
+    let info = &solver.info;
+
+    let is_primal_infeasible = matches!(
+        solution.status,
+        SolverStatus::PrimalInfeasible | SolverStatus::AlmostPrimalInfeasible
+    );
+    let is_dual_infeasible = matches!(
+        solution.status,
+        SolverStatus::DualInfeasible | SolverStatus::AlmostDualInfeasible
+    );
+    // AlmostSolved still carries a usable (if less accurate) point, so
+    // surface it alongside Solved instead of dropping it to None.
+    let has_point = matches!(
+        solution.status,
+        SolverStatus::Solved | SolverStatus::AlmostSolved
+    );
+
+    SolveResult {
+        status: status_to_string(solution.status),
+        obj_val: if has_point {
+            Some(solution.obj_val)
+        } else {
+            None
+        },
+        x: if has_point {
+            Some(solution.x.clone())
+        } else {
+            None
+        },
+        z: if has_point {
+            Some(solution.z.clone())
+        } else {
+            None
+        },
+        s: if has_point {
+            Some(solution.s.clone())
+        } else {
+            None
+        },
+        certificate: if is_primal_infeasible {
+            // Primal-infeasibility certificate: the dual ray z.
+            Some(solution.z.clone())
+        } else if is_dual_infeasible {
+            // Dual-infeasibility certificate: the unbounded primal ray x.
+            Some(solution.x.clone())
+        } else {
+            None
+        },
+        solve_time: info.solve_time,
+        iterations: info.iterations,
+    }
+}
+
+/// Turn a caught solver panic into an error `SolveResult`.
+fn panic_to_solve_result(e: Box<dyn std::any::Any + Send>) -> SolveResult {
+    let msg = if let Some(s) = e.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = e.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "Unknown panic".to_string()
+    };
+    SolveResult {
+        status: format!("error: solver panic: {}", msg),
+        obj_val: None,
+        x: None,
+        z: None,
+        s: None,
+        certificate: None,
+        solve_time: 0.0,
+        iterations: 0,
+    }
+}
+
+/// Stateful solver handle for repeated, related solves.
+///
+/// Wraps a `DefaultSolver` so that repeated solves (e.g. parameter sweeps,
+/// sequential convex programming) can update `q`/`b` in place and reuse the
+/// existing factorization instead of rebuilding the problem from scratch.
+///
+/// This does *not* warm-start the underlying IPM iterate — Clarabel computes
+/// its own starting point from the cones on each `solve()` call, and there is
+/// currently no supported API to seed it from a prior solution.
+#[wasm_bindgen]
+pub struct SolverHandle {
+    solver: DefaultSolver,
+    n: usize,
+    m: usize,
+}
+
+#[wasm_bindgen]
+impl SolverHandle {
+    /// Create a new solver handle. Arguments mirror `solve()`.
+    #[allow(clippy::too_many_arguments)]
+    #[wasm_bindgen(constructor)]
+    pub fn new(
+        p_col_ptr: &[u32],
+        p_row_idx: &[u32],
+        p_values: &[f64],
+        q: &[f64],
+        a_col_ptr: &[u32],
+        a_row_idx: &[u32],
+        a_values: &[f64],
+        b: &[f64],
+        n: u32,
+        m: u32,
+        cone_spec_json: &str,
+        settings_json: &str,
+    ) -> Result<SolverHandle, JsValue> {
+        Self::try_new(
+            p_col_ptr,
+            p_row_idx,
+            p_values,
+            q,
+            a_col_ptr,
+            a_row_idx,
+            a_values,
+            b,
+            n,
+            m,
+            cone_spec_json,
+            settings_json,
+        )
+        .map_err(|e| JsValue::from_str(&e))
+    }
+
+    /// Core of `new()`, split out so it can be exercised in tests without
+    /// going through the `JsValue` boundary.
+    #[allow(clippy::too_many_arguments)]
+    fn try_new(
+        p_col_ptr: &[u32],
+        p_row_idx: &[u32],
+        p_values: &[f64],
+        q: &[f64],
+        a_col_ptr: &[u32],
+        a_row_idx: &[u32],
+        a_values: &[f64],
+        b: &[f64],
+        n: u32,
+        m: u32,
+        cone_spec_json: &str,
+        settings_json: &str,
+    ) -> Result<SolverHandle, String> {
+        let n = n as usize;
+        let m = m as usize;
+
+        let p_col_ptr: Vec<usize> = p_col_ptr.iter().map(|&x| x as usize).collect();
+        let p_row_idx: Vec<usize> = p_row_idx.iter().map(|&x| x as usize).collect();
+        let a_col_ptr: Vec<usize> = a_col_ptr.iter().map(|&x| x as usize).collect();
+        let a_row_idx: Vec<usize> = a_row_idx.iter().map(|&x| x as usize).collect();
+
+        let cone_spec: ConeSpec = serde_json::from_str(cone_spec_json)
+            .map_err(|e| format!("invalid cone spec: {}", e))?;
+        let settings: SolverSettings = serde_json::from_str(settings_json).unwrap_or_default();
+
+        let p = CscMatrix::new(n, n, p_col_ptr, p_row_idx, p_values.to_vec());
+        let a = CscMatrix::new(m, n, a_col_ptr, a_row_idx, a_values.to_vec());
+        let cones = build_cones(&cone_spec).map_err(|e| format!("invalid cone spec: {}", e))?;
+
+        // Use a large but finite time_limit instead of infinity (WASM compatibility)
+        let time_limit = if settings.time_limit.is_infinite() {
+            1e10 // ~317 years, effectively infinite
+        } else {
+            settings.time_limit
+        };
+
+        let solver_settings = DefaultSettingsBuilder::default()
+            .verbose(settings.verbose)
+            .max_iter(settings.max_iter)
+            .time_limit(time_limit)
+            .tol_gap_abs(settings.tol_gap_abs)
+            .tol_gap_rel(settings.tol_gap_rel)
+            .build()
+            .map_err(|e| format!("invalid solver settings: {}", e))?;
+
+        let solver = DefaultSolver::new(&p, q, &a, b, &cones, solver_settings);
+
+        Ok(SolverHandle { solver, n, m })
+    }
+
+    /// Solve (or re-solve) the problem, returning the same JSON shape as `solve()`.
+    pub fn solve(&mut self) -> JsValue {
+        let solve_result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+            self.solver.solve();
+            extract_solve_result(&self.solver)
+        }));
+
+        let result = solve_result.unwrap_or_else(panic_to_solve_result);
+
+        serde_wasm_bindgen::to_value(&result).unwrap()
+    }
+
+    /// Update the linear cost vector `q` in place before the next `solve()`,
+    /// reusing the existing factorization.
+    pub fn update_q(&mut self, q: &[f64]) -> Result<(), JsValue> {
+        self.try_update_q(q).map_err(|e| JsValue::from_str(&e))
+    }
+
+    fn try_update_q(&mut self, q: &[f64]) -> Result<(), String> {
+        if q.len() != self.n {
+            return Err(format!("update_q: q.len() ({}) != n ({})", q.len(), self.n));
+        }
+        self.solver.update_q(q);
+        Ok(())
+    }
+
+    /// Update the constraint vector `b` in place before the next `solve()`,
+    /// reusing the existing factorization.
+    pub fn update_b(&mut self, b: &[f64]) -> Result<(), JsValue> {
+        self.try_update_b(b).map_err(|e| JsValue::from_str(&e))
+    }
+
+    fn try_update_b(&mut self, b: &[f64]) -> Result<(), String> {
+        if b.len() != self.m {
+            return Err(format!("update_b: b.len() ({}) != m ({})", b.len(), self.m));
+        }
+        self.solver.update_b(b);
+        Ok(())
+    }
+}
+
 /// Simple test function to verify WASM is working
 #[wasm_bindgen]
 pub fn test_wasm() -> String {
@@ -257,3 +490,161 @@ pub fn test_wasm() -> String {
 pub fn version() -> String {
     "clarabel-wasm 0.1.0".to_string()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spec_with_genpower(genpower: Vec<(Vec<f64>, usize)>) -> ConeSpec {
+        ConeSpec {
+            zero: 0,
+            nonneg: 0,
+            soc: Vec::new(),
+            exp: 0,
+            power: Vec::new(),
+            genpower,
+        }
+    }
+
+    #[test]
+    fn genpower_cone_accepts_valid_alpha() {
+        let spec = spec_with_genpower(vec![(vec![0.5, 0.5], 2)]);
+        assert!(build_cones(&spec).is_ok());
+    }
+
+    #[test]
+    fn genpower_cone_rejects_non_positive_alpha() {
+        let spec = spec_with_genpower(vec![(vec![1.0, 0.0], 1)]);
+        assert!(build_cones(&spec).is_err());
+    }
+
+    #[test]
+    fn genpower_cone_rejects_alpha_not_summing_to_one() {
+        let spec = spec_with_genpower(vec![(vec![0.3, 0.3], 1)]);
+        assert!(build_cones(&spec).is_err());
+    }
+
+    #[test]
+    fn status_to_string_covers_inaccurate_and_limit_statuses() {
+        assert_eq!(status_to_string(SolverStatus::Solved), "optimal");
+        assert_eq!(status_to_string(SolverStatus::AlmostSolved), "solved_inaccurate");
+        assert_eq!(
+            status_to_string(SolverStatus::AlmostPrimalInfeasible),
+            "primal_infeasible_inaccurate"
+        );
+        assert_eq!(
+            status_to_string(SolverStatus::AlmostDualInfeasible),
+            "dual_infeasible_inaccurate"
+        );
+        assert_eq!(status_to_string(SolverStatus::MaxIterations), "max_iterations");
+        assert_eq!(status_to_string(SolverStatus::MaxTime), "max_time");
+    }
+
+    #[test]
+    fn extract_solve_result_surfaces_point_when_solved() {
+        // min x s.t. x >= 1, i.e. -x + s = -1, s >= 0 (same problem as
+        // wasm/examples/test_solver.rs).
+        let p = CscMatrix::new(1, 1, vec![0, 0], vec![], vec![]);
+        let q = vec![1.0];
+        let a = CscMatrix::new(1, 1, vec![0, 1], vec![0], vec![-1.0]);
+        let b = vec![-1.0];
+        let cones = vec![NonnegativeConeT(1)];
+        let settings = DefaultSettingsBuilder::default().build().unwrap();
+        let mut solver = DefaultSolver::new(&p, &q, &a, &b, &cones, settings);
+        solver.solve();
+        let result = extract_solve_result(&solver);
+
+        assert_eq!(result.status, "optimal");
+        assert!(result.obj_val.is_some());
+        assert!(result.x.is_some());
+        assert!(result.certificate.is_none());
+    }
+
+    #[test]
+    fn extract_solve_result_surfaces_certificate_when_primal_infeasible() {
+        // x >= 1 and x <= -1: contradictory, so primal-infeasible.
+        let p = CscMatrix::new(1, 1, vec![0, 0], vec![], vec![]);
+        let q = vec![0.0];
+        let a = CscMatrix::new(2, 1, vec![0, 2], vec![0, 1], vec![-1.0, 1.0]);
+        let b = vec![-1.0, -1.0];
+        let cones = vec![NonnegativeConeT(2)];
+        let settings = DefaultSettingsBuilder::default().build().unwrap();
+        let mut solver = DefaultSolver::new(&p, &q, &a, &b, &cones, settings);
+        solver.solve();
+        let result = extract_solve_result(&solver);
+
+        assert!(result.obj_val.is_none());
+        assert!(result.x.is_none());
+        assert!(result.certificate.is_some());
+    }
+
+    #[test]
+    fn extract_solve_result_surfaces_certificate_when_dual_infeasible() {
+        // min -x s.t. x >= 0: unbounded below, so dual-infeasible.
+        let p = CscMatrix::new(1, 1, vec![0, 0], vec![], vec![]);
+        let q = vec![-1.0];
+        let a = CscMatrix::new(1, 1, vec![0, 1], vec![0], vec![-1.0]);
+        let b = vec![0.0];
+        let cones = vec![NonnegativeConeT(1)];
+        let settings = DefaultSettingsBuilder::default().build().unwrap();
+        let mut solver = DefaultSolver::new(&p, &q, &a, &b, &cones, settings);
+        solver.solve();
+        let result = extract_solve_result(&solver);
+
+        assert!(result.obj_val.is_none());
+        assert!(result.certificate.is_some());
+    }
+
+    fn trivial_cone_spec_json() -> String {
+        "{\"zero\":0,\"nonneg\":1,\"soc\":[],\"exp\":0,\"power\":[]}".to_string()
+    }
+
+    // min x s.t. x >= 1, via SolverHandle::try_new (same problem as
+    // wasm/examples/test_solver.rs).
+    fn trivial_solver_handle() -> SolverHandle {
+        SolverHandle::try_new(
+            &[0, 0],
+            &[],
+            &[],
+            &[1.0],
+            &[0, 1],
+            &[0],
+            &[-1.0],
+            &[-1.0],
+            1,
+            1,
+            &trivial_cone_spec_json(),
+            "{}",
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn solver_handle_try_new_builds_a_working_solver() {
+        let mut handle = trivial_solver_handle();
+        handle.solver.solve();
+        let result = extract_solve_result(&handle.solver);
+        assert_eq!(result.status, "optimal");
+    }
+
+    #[test]
+    fn solver_handle_update_q_rejects_wrong_length() {
+        let mut handle = trivial_solver_handle();
+        assert!(handle.try_update_q(&[1.0, 2.0]).is_err());
+    }
+
+    #[test]
+    fn solver_handle_update_b_rejects_wrong_length() {
+        let mut handle = trivial_solver_handle();
+        assert!(handle.try_update_b(&[-1.0, -2.0]).is_err());
+    }
+
+    #[test]
+    fn solver_handle_update_q_accepts_matching_length_and_resolves() {
+        let mut handle = trivial_solver_handle();
+        assert!(handle.try_update_q(&[2.0]).is_ok());
+        handle.solver.solve();
+        let result = extract_solve_result(&handle.solver);
+        assert_eq!(result.status, "optimal");
+    }
+}